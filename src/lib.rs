@@ -1,4 +1,6 @@
-use std::cell::{Ref, RefCell, RefMut};
+use std::cell::{BorrowError, BorrowMutError, Ref, RefCell, RefMut, UnsafeCell};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 
 pub struct DoubleBuffer<T> {
@@ -50,12 +52,267 @@ impl<T> DoubleBuffer<T> {
         self.switched = !self.switched;
     }
 
+    /// Try to borrow the current-state buffer immutably, returning an error instead of panicking
+    /// if a conflicting mutable borrow (from `next()` or `try_next()`) is already live.
+    pub fn try_current(&self) -> Result<Ref<T>, BorrowError> {
+        match self.switched {
+            false => self.first.try_borrow(),
+            true => self.second.try_borrow(),
+        }
+    }
+
+    /// Try to borrow the next-state buffer mutably, returning an error instead of panicking if
+    /// a conflicting borrow (from `current()`, `next()`, or their `try_` counterparts) is already
+    /// live.
+    pub fn try_next(&self) -> Result<RefMut<T>, BorrowMutError> {
+        match self.switched {
+            false => self.second.try_borrow_mut(),
+            true => self.first.try_borrow_mut(),
+        }
+    }
+
+    /// Borrow the current-state buffer immutably and the next-state buffer mutably at the same
+    /// time, passing both to a closure, then drop both borrows.  This lets you drive an update
+    /// step in a single expression instead of interleaving separate `current()` and `next()` calls.
+    pub fn apply(&self, f: impl FnOnce(&T, &mut T)) {
+        let current = self.current();
+        let mut next = self.next();
+        f(&current, &mut next);
+    }
+
+    /// Borrow the current-state and next-state buffers together, as a `(Ref<T>, RefMut<T>)` pair,
+    /// so callers that need both guards at once don't have to call `current()` and `next()`
+    /// separately.
+    pub fn split(&self) -> (Ref<T>, RefMut<T>) {
+        (self.current(), self.next())
+    }
+
+}
+
+impl<T: Clone> DoubleBuffer<T> {
+
+    /// Clone the current-state buffer's contents into the next-state buffer, so the next turn of
+    /// the simulation starts from a consistent copy of the previous state instead of whatever
+    /// stale data the next buffer held two turns ago.
+    pub fn commit(&mut self) {
+        let copy = self.current().clone();
+        *self.next() = copy;
+    }
+
+}
+
+impl<T: Default> Default for DoubleBuffer<T> {
+
+    /// Create an empty buffer, with both the current and next state set to `T::default()`.
+    fn default() -> Self {
+        Self::new(T::default(), T::default())
+    }
+
+}
+
+
+pub struct SwapBuffer<T> {
+    buffers: [T; 2],
+    old_index: usize,
+    new_index: usize,
+}
+
+/// An allocation-free alternative to `DoubleBuffer` for tight simulation loops where you already
+/// hold `&mut self` at swap time. Both slots live in a single `[T; 2]` array allocated once at
+/// construction, and `current()`/`next()` borrow from `&self`/`&mut self` directly, so exclusivity
+/// is enforced by the borrow checker at compile time instead of by a `RefCell` at runtime.
+impl<T> SwapBuffer<T> {
+
+    pub fn new(current: T, next: T) -> Self {
+        Self {
+            buffers: [current, next],
+            old_index: 0,
+            new_index: 1,
+        }
+    }
+
+    /// Get an immutable reference to the current-state buffer.
+    pub fn current(&self) -> &T {
+        &self.buffers[self.old_index]
+    }
+
+    /// Get a mutable reference to the next-state buffer.
+    #[allow(clippy::should_implement_trait)] // mirrors DoubleBuffer::next's naming; not an Iterator
+    pub fn next(&mut self) -> &mut T {
+        &mut self.buffers[self.new_index]
+    }
+
+    /// Switch the "current" and "next" buffers.
+    pub fn switch(&mut self) {
+        std::mem::swap(&mut self.old_index, &mut self.new_index);
+    }
+
+}
+
+impl<T: Default> Default for SwapBuffer<T> {
+
+    /// Create an empty buffer, with both the current and next state set to `T::default()`.
+    fn default() -> Self {
+        Self::new(T::default(), T::default())
+    }
+
+}
+
+
+const TRIPLE_INDEX_MASK: usize = 0b011;
+const TRIPLE_NEW_DATA_FLAG: usize = 0b100;
+
+struct TripleBufferState<T> {
+    buffers: [UnsafeCell<T>; 3],
+    /// The index (in the low two bits) of the shared slot, plus a flag bit (`TRIPLE_NEW_DATA_FLAG`)
+    /// that is set while that slot holds published data the consumer has not claimed yet; once the
+    /// consumer fetches, the shared slot is the consumer's released front slot and the flag is clear.
+    shared_index: AtomicUsize,
+}
+
+// SAFETY: a slot is only ever read or written through the unique `Producer`/`Consumer` that
+// currently owns its index, and `shared_index` hands slot ownership off atomically, so sharing
+// `&TripleBufferState<T>` across threads is sound as long as `T` itself is safe to send between
+// threads.
+unsafe impl<T: Send> Sync for TripleBufferState<T> {}
+
+/// The producer half of a triple buffer created by [`triple_buffer`].
+pub struct Producer<T> {
+    state: Arc<TripleBufferState<T>>,
+    back_index: usize,
+}
+
+/// The consumer half of a triple buffer created by [`triple_buffer`].
+pub struct Consumer<T> {
+    state: Arc<TripleBufferState<T>>,
+    front_index: usize,
+}
+
+/// Create a thread-safe triple buffer for wait-free handoff between one producer thread and one
+/// consumer thread, with no locks and no torn reads, and split it into its `Producer`/`Consumer`
+/// halves.
+///
+/// The producer writes into its private back slot and calls `publish()` to atomically exchange it
+/// with the shared "ready" slot; the consumer calls `fetch()` to atomically claim the latest ready
+/// slot into its private front slot. The three slots are always distinct, so neither side ever
+/// blocks on the other. Exactly one producer and one consumer are supported, which is why
+/// `Producer<T>` and `Consumer<T>` are separate, non-`Clone` handles.
+pub fn triple_buffer<T>(a: T, b: T, c: T) -> (Producer<T>, Consumer<T>) {
+    let state = Arc::new(TripleBufferState {
+        buffers: [UnsafeCell::new(a), UnsafeCell::new(b), UnsafeCell::new(c)],
+        shared_index: AtomicUsize::new(2),
+    });
+    let producer = Producer { state: state.clone(), back_index: 0 };
+    let consumer = Consumer { state, front_index: 1 };
+    (producer, consumer)
+}
+
+impl<T> Producer<T> {
+
+    /// Get a mutable reference to the producer's private back slot.
+    pub fn back_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.state.buffers[self.back_index].get() }
+    }
+
+    /// Publish the back slot to the shared "ready" slot for the consumer to fetch.
+    pub fn publish(&mut self) {
+        let published = self.state.shared_index.swap(self.back_index | TRIPLE_NEW_DATA_FLAG, Ordering::AcqRel);
+        self.back_index = published & TRIPLE_INDEX_MASK;
+    }
+
+}
+
+impl<T> Consumer<T> {
+
+    /// Get an immutable reference to the consumer's private front slot.
+    pub fn front(&self) -> &T {
+        unsafe { &*self.state.buffers[self.front_index].get() }
+    }
+
+    /// Claim the latest published slot into the front slot, if there is one. Returns whether the
+    /// front slot was updated.
+    pub fn fetch(&mut self) -> bool {
+        if self.state.shared_index.load(Ordering::Acquire) & TRIPLE_NEW_DATA_FLAG == 0 {
+            return false;
+        }
+        let claimed = self.state.shared_index.swap(self.front_index, Ordering::AcqRel);
+        self.front_index = claimed & TRIPLE_INDEX_MASK;
+        true
+    }
+
+}
+
+
+pub struct GridBuffer<T> {
+    cells: Vec<[T; 2]>,
+    width: usize,
+    height: usize,
+    parity: bool,
+}
+
+/// A specialized double buffer for grid simulations like Conway's Game of Life, keeping each
+/// cell's current and next value interleaved in a single `[T; 2]` slot so they share a cache line,
+/// unlike the separate allocations a generic `DoubleBuffer<Vec<T>>` would need. A parity flag
+/// chosen at each `switch()` picks which half of every slot is "current"; switching copies no data.
+impl<T: Clone> GridBuffer<T> {
+
+    pub fn new(width: usize, height: usize, initial: T) -> Self {
+        Self {
+            cells: vec![[initial.clone(), initial]; width * height],
+            width,
+            height,
+            parity: false,
+        }
+    }
+
+    fn index(&self, x: usize, y: usize) -> usize {
+        y * self.width + x
+    }
+
+    /// Get the current value of the cell at `(x, y)`.
+    pub fn get(&self, x: usize, y: usize) -> &T {
+        let i = self.index(x, y);
+        &self.cells[i][self.parity as usize]
+    }
+
+    /// Set the next value of the cell at `(x, y)`.
+    pub fn set(&mut self, x: usize, y: usize, value: T) {
+        let i = self.index(x, y);
+        self.cells[i][!self.parity as usize] = value;
+    }
+
+    /// The coordinates of the up-to-8 in-bounds Moore neighbors of `(x, y)`.
+    pub fn neighbors(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut result = Vec::with_capacity(8);
+        for dy in -1i64..=1 {
+            for dx in -1i64..=1 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let nx = x as i64 + dx;
+                let ny = y as i64 + dy;
+                if nx >= 0 && ny >= 0 && (nx as usize) < self.width && (ny as usize) < self.height {
+                    result.push((nx as usize, ny as usize));
+                }
+            }
+        }
+        result
+    }
+
+    /// Switch the "current" and "next" halves of every cell by flipping the parity flag.
+    pub fn switch(&mut self) {
+        self.parity = !self.parity;
+    }
+
 }
 
 
 #[cfg(test)]
 mod tests {
     use crate::DoubleBuffer;
+    use crate::SwapBuffer;
+    use crate::{triple_buffer, Producer, Consumer};
+    use crate::GridBuffer;
 
 
     #[test]
@@ -88,4 +345,133 @@ mod tests {
      }
 
 
+    #[test]
+    fn apply_works() {
+        let mut my_double_buf: DoubleBuffer<Vec<i32>> = DoubleBuffer::new(vec!(2,4,6), Vec::new());
+        my_double_buf.apply(|current, next| {
+            for number in current.iter() {
+                next.push(*number + 1);
+            }
+        });
+        my_double_buf.switch();
+        assert_eq!(*my_double_buf.current(), vec!(3,5,7));
+    }
+
+
+    #[test]
+    fn commit_works() {
+        let mut my_double_buf: DoubleBuffer<Vec<i32>> = DoubleBuffer::new(vec!(2,4,6), vec!(9,9,9));
+        my_double_buf.commit();
+        assert_eq!(*my_double_buf.next(), vec!(2,4,6));
+    }
+
+
+    #[test]
+    fn default_works() {
+        let my_double_buf: DoubleBuffer<i32> = DoubleBuffer::default();
+        assert_eq!(*my_double_buf.current(), 0);
+        assert_eq!(*my_double_buf.next(), 0);
+    }
+
+
+    #[test]
+    fn try_current_and_try_next_work() {
+        let my_double_buf: DoubleBuffer<i32> = DoubleBuffer::new(1, 0);
+        assert!(my_double_buf.try_current().is_ok());
+        let _next = my_double_buf.try_next().unwrap();
+        assert!(my_double_buf.try_next().is_err());
+    }
+
+
+    #[test]
+    fn split_works() {
+        let my_double_buf: DoubleBuffer<i32> = DoubleBuffer::new(1, 0);
+        let (current, mut next) = my_double_buf.split();
+        *next = *current + 1;
+        drop((current, next));
+        assert_eq!(*my_double_buf.current(), 1);
+    }
+
+
+    #[test]
+    fn swap_buffer_switching_works() {
+        let mut my_swap_buf: SwapBuffer<i32> = SwapBuffer::new(0, 0);
+        *my_swap_buf.next() += 10;
+        assert_eq!(*my_swap_buf.current(), 0);
+        my_swap_buf.switch();
+        assert_eq!(*my_swap_buf.current(), 10);
+        my_swap_buf.switch();
+        assert_eq!(*my_swap_buf.current(), 0);
+    }
+
+
+    #[test]
+    fn swap_buffer_writing_from_current_to_next_works() {
+        let mut my_swap_buf: SwapBuffer<Vec<i32>> = SwapBuffer::new(vec!(2,4,6), Vec::new());
+        for number in my_swap_buf.current().clone().iter() {
+            my_swap_buf.next().push(*number + 1);
+        }
+        my_swap_buf.switch();
+        assert_eq!(*my_swap_buf.current(), vec!(3,5,7));
+    }
+
+
+    #[test]
+    fn swap_buffer_default_works() {
+        let my_swap_buf: SwapBuffer<i32> = SwapBuffer::default();
+        assert_eq!(*my_swap_buf.current(), 0);
+    }
+
+
+    #[test]
+    fn triple_buffer_fetch_returns_false_with_nothing_published() {
+        let (_producer, mut consumer) = triple_buffer::<i32>(0, 0, 0);
+        assert!(!consumer.fetch());
+    }
+
+
+    #[test]
+    fn triple_buffer_publish_and_fetch_work() {
+        let (mut producer, mut consumer) = triple_buffer::<i32>(0, 0, 0);
+        *producer.back_mut() = 10;
+        producer.publish();
+        assert!(consumer.fetch());
+        assert_eq!(*consumer.front(), 10);
+
+        *producer.back_mut() = 20;
+        producer.publish();
+        *producer.back_mut() = 30;
+        producer.publish();
+        assert!(consumer.fetch());
+        assert_eq!(*consumer.front(), 30);
+        assert!(!consumer.fetch());
+    }
+
+
+    #[test]
+    fn triple_buffer_producer_and_consumer_are_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Producer<i32>>();
+        assert_send_sync::<Consumer<i32>>();
+    }
+
+
+    #[test]
+    fn grid_buffer_switching_works() {
+        let mut my_grid_buf: GridBuffer<bool> = GridBuffer::new(3, 3, false);
+        my_grid_buf.set(1, 1, true);
+        assert!(!*my_grid_buf.get(1, 1));
+        my_grid_buf.switch();
+        assert!(*my_grid_buf.get(1, 1));
+    }
+
+
+    #[test]
+    fn grid_buffer_neighbors_works() {
+        let my_grid_buf: GridBuffer<bool> = GridBuffer::new(3, 3, false);
+        assert_eq!(my_grid_buf.neighbors(0, 0).len(), 3);
+        assert_eq!(my_grid_buf.neighbors(1, 1).len(), 8);
+    }
+
+
 }